@@ -2,35 +2,133 @@ use anyhow::{Context, Result};
 use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicI64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
 use tokio::sync::{oneshot, Mutex};
 
 use crate::transport::Transport;
 use crate::types::*;
 
+type BoxFuture<'a, O> = Pin<Box<dyn Future<Output = O> + Send + 'a>>;
+type PendingRequests = Arc<Mutex<HashMap<i64, oneshot::Sender<JsonRpcResponse>>>>;
+
+/// Handler invoked for server-initiated notifications (`method`, `params`).
+pub type NotificationHandler = Arc<dyn Fn(&str, Option<Value>) + Send + Sync>;
+
+/// Handler invoked for server-initiated requests. The returned value becomes
+/// the `result` of the `JsonRpcResponse` sent back to the server.
+pub type ServerRequestHandler =
+    Arc<dyn Fn(String, Option<Value>) -> BoxFuture<'static, Result<Value>> + Send + Sync>;
+
+/// Configuration for `McpClient`.
+#[derive(Debug, Clone)]
+pub struct McpClientConfig {
+    /// How long to wait for a response before a `request` call times out.
+    pub request_timeout: Duration,
+}
+
+impl Default for McpClientConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Error returned when a request does not receive a response within
+/// `McpClientConfig::request_timeout`.
+#[derive(Debug)]
+pub struct RequestTimeoutError {
+    pub id: i64,
+}
+
+impl std::fmt::Display for RequestTimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Request {} timed out waiting for a response", self.id)
+    }
+}
+
+impl std::error::Error for RequestTimeoutError {}
+
+/// A handle to an in-flight request, allowing the caller to cancel it
+/// cooperatively before a response arrives.
+pub struct CancellationToken<T: Transport> {
+    id: i64,
+    transport: Arc<T>,
+    pending_requests: PendingRequests,
+}
+
+impl<T: Transport + Send + Sync + 'static> CancellationToken<T> {
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Cancel the request this token was issued for, sending
+    /// `notifications/cancelled` to the server if it is still pending.
+    pub async fn cancel(&self) -> Result<()> {
+        cancel_request(&self.pending_requests, &self.transport, self.id).await
+    }
+}
+
+async fn cancel_request<T: Transport + Send + Sync + 'static>(
+    pending_requests: &PendingRequests,
+    transport: &Arc<T>,
+    id: i64,
+) -> Result<()> {
+    let removed = pending_requests.lock().await.remove(&id);
+    if removed.is_some() {
+        transport
+            .send(JsonRpcRequest {
+                jsonrpc: "2.0".to_string(),
+                id: None,
+                method: "notifications/cancelled".to_string(),
+                params: Some(serde_json::json!({ "requestId": id })),
+            })
+            .await?;
+    }
+    Ok(())
+}
+
 pub struct McpClient<T: Transport> {
     transport: Arc<T>,
     next_id: AtomicI64,
-    pending_requests: Arc<Mutex<HashMap<i64, oneshot::Sender<JsonRpcResponse>>>>,
+    pending_requests: PendingRequests,
+    notification_handler: Arc<StdMutex<Option<NotificationHandler>>>,
+    request_handler: Arc<StdMutex<Option<ServerRequestHandler>>>,
+    config: McpClientConfig,
+    init_result: StdMutex<Option<InitializeResult>>,
 }
 
 impl<T: Transport + Send + Sync + 'static> McpClient<T> {
     pub fn new(transport: T) -> Self {
+        Self::with_config(transport, McpClientConfig::default())
+    }
+
+    pub fn with_config(transport: T, config: McpClientConfig) -> Self {
         let client = Self {
             transport: Arc::new(transport),
             next_id: AtomicI64::new(1),
             pending_requests: Arc::new(Mutex::new(HashMap::new())),
+            notification_handler: Arc::new(StdMutex::new(None)),
+            request_handler: Arc::new(StdMutex::new(None)),
+            config,
+            init_result: StdMutex::new(None),
         };
 
-        // Spawn a background task to read responses
+        // Spawn a background task to read responses, notifications and
+        // server-initiated requests off the transport.
         let transport_clone = client.transport.clone();
         let pending_clone = client.pending_requests.clone();
+        let notification_clone = client.notification_handler.clone();
+        let request_handler_clone = client.request_handler.clone();
 
         tokio::spawn(async move {
             loop {
-                match transport_clone.receive::<JsonRpcResponse>().await {
-                    Ok(response) => {
+                match transport_clone.receive::<IncomingMessage>().await {
+                    Ok(IncomingMessage::Response(response)) => {
                         if let Some(id_val) = response.id.clone() {
                             if let Some(id) = id_val.as_i64() {
                                 let mut pending = pending_clone.lock().await;
@@ -40,6 +138,20 @@ impl<T: Transport + Send + Sync + 'static> McpClient<T> {
                             }
                         }
                     }
+                    Ok(IncomingMessage::Notification(notification)) => {
+                        let handler = notification_clone.lock().unwrap().clone();
+                        if let Some(handler) = handler {
+                            handler(&notification.method, notification.params);
+                        }
+                    }
+                    Ok(IncomingMessage::Request(request)) => {
+                        let handler = request_handler_clone.lock().unwrap().clone();
+                        let transport = transport_clone.clone();
+                        tokio::spawn(async move {
+                            let response = Self::handle_server_request(handler, request).await;
+                            let _ = transport.send(response).await;
+                        });
+                    }
                     Err(e) => {
                         eprintln!("MCP Client Transport Error: {:?}", e);
                         break;
@@ -51,11 +163,84 @@ impl<T: Transport + Send + Sync + 'static> McpClient<T> {
         client
     }
 
+    /// Register a handler for server-initiated notifications
+    /// (e.g. `notifications/progress`, `notifications/message`).
+    pub fn on_notification<F>(&self, handler: F)
+    where
+        F: Fn(&str, Option<Value>) + Send + Sync + 'static,
+    {
+        *self.notification_handler.lock().unwrap() = Some(Arc::new(handler));
+    }
+
+    /// Register a handler for server-initiated requests
+    /// (e.g. `sampling/createMessage`, `roots/list`, elicitation).
+    pub fn on_request<F, Fut>(&self, handler: F)
+    where
+        F: Fn(String, Option<Value>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value>> + Send + 'static,
+    {
+        let handler: ServerRequestHandler = Arc::new(move |method, params| {
+            Box::pin(handler(method, params)) as BoxFuture<'static, Result<Value>>
+        });
+        *self.request_handler.lock().unwrap() = Some(handler);
+    }
+
+    async fn handle_server_request(
+        handler: Option<ServerRequestHandler>,
+        request: JsonRpcRequest,
+    ) -> JsonRpcResponse {
+        let id = request.id.clone();
+        let Some(handler) = handler else {
+            return JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32601,
+                    message: format!("Method not found: {}", request.method),
+                    data: None,
+                }),
+            };
+        };
+
+        match handler(request.method, request.params).await {
+            Ok(result) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: Some(result),
+                error: None,
+            },
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32603,
+                    message: e.to_string(),
+                    data: None,
+                }),
+            },
+        }
+    }
+
     async fn request<P: Serialize + Send + Sync>(
         &self,
         method: &str,
         params: Option<P>,
     ) -> Result<Value> {
+        let (_id, value) = self.request_with_id(method, params).await?;
+        Ok(value)
+    }
+
+    /// Like `request`, but also returns the JSON-RPC id that was assigned.
+    /// The id is returned only once the response has arrived, so it cannot
+    /// be used to cancel this request; `call_tool_cancellable` hands out a
+    /// `CancellationToken` while its request is still in flight.
+    async fn request_with_id<P: Serialize + Send + Sync>(
+        &self,
+        method: &str,
+        params: Option<P>,
+    ) -> Result<(i64, Value)> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let params_value = match params {
             Some(p) => Some(serde_json::to_value(p)?),
@@ -77,9 +262,13 @@ impl<T: Transport + Send + Sync + 'static> McpClient<T> {
 
         self.transport.send(request).await?;
 
-        let response = rx
-            .await
-            .context("Failed to receive response from MCP server")?;
+        let response = match tokio::time::timeout(self.config.request_timeout, rx).await {
+            Ok(received) => received.context("Failed to receive response from MCP server")?,
+            Err(_) => {
+                cancel_request(&self.pending_requests, &self.transport, id).await?;
+                return Err(RequestTimeoutError { id }.into());
+            }
+        };
 
         if let Some(error) = response.error {
             return Err(anyhow::anyhow!(
@@ -89,12 +278,17 @@ impl<T: Transport + Send + Sync + 'static> McpClient<T> {
             ));
         }
 
-        Ok(response.result.unwrap_or(Value::Null))
+        Ok((id, response.result.unwrap_or(Value::Null)))
+    }
+
+    /// Cancel a request by its JSON-RPC id, sending `notifications/cancelled`
+    /// to the server if it is still pending.
+    pub async fn cancel(&self, id: i64) -> Result<()> {
+        cancel_request(&self.pending_requests, &self.transport, id).await
     }
 
     pub async fn initialize(&self) -> Result<()> {
-        // Minimal init for now
-        let _ = self
+        let result = self
             .request::<Value>(
                 "initialize",
                 Some(serde_json::json!({
@@ -105,6 +299,19 @@ impl<T: Transport + Send + Sync + 'static> McpClient<T> {
             )
             .await?;
 
+        let init_result: InitializeResult =
+            serde_json::from_value(result).context("Failed to parse initialize result")?;
+
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&init_result.protocol_version.as_str()) {
+            return Err(anyhow::anyhow!(
+                "Unsupported MCP protocol version {:?}: this client supports {:?}",
+                init_result.protocol_version,
+                SUPPORTED_PROTOCOL_VERSIONS
+            ));
+        }
+
+        *self.init_result.lock().unwrap() = Some(init_result);
+
         // Send initialized notification
         self.transport
             .send(JsonRpcRequest {
@@ -118,12 +325,79 @@ impl<T: Transport + Send + Sync + 'static> McpClient<T> {
         Ok(())
     }
 
+    /// The capabilities the server advertised in its `initialize` response,
+    /// if `initialize` has been called.
+    pub fn server_capabilities(&self) -> Option<ServerCapabilities> {
+        self.init_result
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|r| r.capabilities.clone())
+    }
+
+    /// The protocol version negotiated with the server during `initialize`.
+    pub fn protocol_version(&self) -> Option<String> {
+        self.init_result
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|r| r.protocol_version.clone())
+    }
+
+    /// Return an error if the server hasn't advertised `capability` in its
+    /// `initialize` response, instead of sending a request it cannot answer.
+    fn ensure_capability(&self, capability: &str) -> Result<()> {
+        let Some(init_result) = self.init_result.lock().unwrap().clone() else {
+            return Ok(());
+        };
+
+        let advertised = match capability {
+            "tools" => init_result.capabilities.tools.is_some(),
+            "resources" => init_result.capabilities.resources.is_some(),
+            "prompts" => init_result.capabilities.prompts.is_some(),
+            _ => true,
+        };
+
+        if !advertised {
+            return Err(anyhow::anyhow!(
+                "server does not advertise {} capability",
+                capability
+            ));
+        }
+
+        Ok(())
+    }
+
     pub async fn list_tools(&self) -> Result<ListToolsResult> {
-        let result = self.request::<()>("tools/list", None).await?;
+        self.list_tools_page(None).await
+    }
+
+    /// Follow `next_cursor` until the server stops returning one,
+    /// collecting every page of tools. `list_tools` only returns the first
+    /// page, which silently truncates large tool lists.
+    pub async fn list_all_tools(&self) -> Result<Vec<ToolDefinition>> {
+        let mut tools = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self.list_tools_page(cursor).await?;
+            tools.extend(page.tools);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(tools)
+    }
+
+    async fn list_tools_page(&self, cursor: Option<String>) -> Result<ListToolsResult> {
+        self.ensure_capability("tools")?;
+        let params = cursor.map(|cursor| serde_json::json!({ "cursor": cursor }));
+        let result = self.request("tools/list", params).await?;
         serde_json::from_value(result).context("Failed to parse list_tools result")
     }
 
     pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<CallToolResult> {
+        self.ensure_capability("tools")?;
         let params = CallToolRequest {
             name: name.to_string(),
             arguments,
@@ -132,6 +406,150 @@ impl<T: Transport + Send + Sync + 'static> McpClient<T> {
         serde_json::from_value(result).context("Failed to parse call_tool result")
     }
 
+    /// Like `call_tool`, but returns immediately with a `CancellationToken`
+    /// plus a `JoinHandle` for the eventual result, so a long-running
+    /// `tools/call` invocation can be aborted cooperatively from another
+    /// task while this one is still in flight.
+    pub async fn call_tool_cancellable(
+        &self,
+        name: &str,
+        arguments: Value,
+    ) -> Result<(
+        CancellationToken<T>,
+        tokio::task::JoinHandle<Result<CallToolResult>>,
+    )> {
+        self.ensure_capability("tools")?;
+        let params = CallToolRequest {
+            name: name.to_string(),
+            arguments,
+        };
+        let params_value = serde_json::to_value(params)?;
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::Number(id.into())),
+            method: "tools/call".to_string(),
+            params: Some(params_value),
+        };
+
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut pending = self.pending_requests.lock().await;
+            pending.insert(id, tx);
+        }
+        self.transport.send(request).await?;
+
+        let token = CancellationToken {
+            id,
+            transport: self.transport.clone(),
+            pending_requests: self.pending_requests.clone(),
+        };
+
+        let pending_requests = self.pending_requests.clone();
+        let transport = self.transport.clone();
+        let timeout = self.config.request_timeout;
+        let handle = tokio::spawn(async move {
+            let response = match tokio::time::timeout(timeout, rx).await {
+                Ok(received) => received.context("Failed to receive response from MCP server")?,
+                Err(_) => {
+                    cancel_request(&pending_requests, &transport, id).await?;
+                    return Err(RequestTimeoutError { id }.into());
+                }
+            };
+
+            if let Some(error) = response.error {
+                return Err(anyhow::anyhow!(
+                    "MCP Error {}: {}",
+                    error.code,
+                    error.message
+                ));
+            }
+
+            serde_json::from_value(response.result.unwrap_or(Value::Null))
+                .context("Failed to parse call_tool result")
+        });
+
+        Ok((token, handle))
+    }
+
+    pub async fn list_resources(&self) -> Result<ListResourcesResult> {
+        self.list_resources_page(None).await
+    }
+
+    /// Follow `next_cursor` until the server stops returning one, collecting
+    /// every page of resources.
+    pub async fn list_all_resources(&self) -> Result<Vec<Resource>> {
+        let mut resources = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self.list_resources_page(cursor).await?;
+            resources.extend(page.resources);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(resources)
+    }
+
+    async fn list_resources_page(&self, cursor: Option<String>) -> Result<ListResourcesResult> {
+        self.ensure_capability("resources")?;
+        let params = cursor.map(|cursor| serde_json::json!({ "cursor": cursor }));
+        let result = self.request("resources/list", params).await?;
+        serde_json::from_value(result).context("Failed to parse list_resources result")
+    }
+
+    pub async fn read_resource(&self, uri: &str) -> Result<ReadResourceResult> {
+        self.ensure_capability("resources")?;
+        let params = ReadResourceRequest {
+            uri: uri.to_string(),
+        };
+        let result = self.request("resources/read", Some(params)).await?;
+        serde_json::from_value(result).context("Failed to parse read_resource result")
+    }
+
+    pub async fn list_prompts(&self) -> Result<ListPromptsResult> {
+        self.list_prompts_page(None).await
+    }
+
+    /// Follow `next_cursor` until the server stops returning one, collecting
+    /// every page of prompts.
+    pub async fn list_all_prompts(&self) -> Result<Vec<Prompt>> {
+        let mut prompts = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self.list_prompts_page(cursor).await?;
+            prompts.extend(page.prompts);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(prompts)
+    }
+
+    async fn list_prompts_page(&self, cursor: Option<String>) -> Result<ListPromptsResult> {
+        self.ensure_capability("prompts")?;
+        let params = cursor.map(|cursor| serde_json::json!({ "cursor": cursor }));
+        let result = self.request("prompts/list", params).await?;
+        serde_json::from_value(result).context("Failed to parse list_prompts result")
+    }
+
+    pub async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: Option<Value>,
+    ) -> Result<GetPromptResult> {
+        self.ensure_capability("prompts")?;
+        let params = GetPromptRequest {
+            name: name.to_string(),
+            arguments,
+        };
+        let result = self.request("prompts/get", Some(params)).await?;
+        serde_json::from_value(result).context("Failed to parse get_prompt result")
+    }
+
     pub async fn shutdown(&self) -> Result<()> {
         let shutdown_result = self.request::<()>("shutdown", None).await;
         let _ = self
@@ -159,3 +577,179 @@ impl<T: Transport + Send + Sync + 'static> McpClient<T> {
         close_result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde::de::DeserializeOwned;
+    use std::collections::VecDeque;
+    use std::sync::atomic::AtomicBool;
+    use tokio::sync::mpsc;
+
+    /// A transport that replies to each outgoing request with the next
+    /// queued canned result, one per request it expects to see (in the order
+    /// requests are sent). Unlike replaying pre-built `JsonRpcResponse`
+    /// values, the response is only produced once `send` actually delivers
+    /// the matching request, so there's no race with the client's read loop
+    /// polling `receive` before the request exists.
+    struct ScriptedTransport {
+        results: Mutex<VecDeque<Value>>,
+        response_tx: mpsc::UnboundedSender<Value>,
+        response_rx: Mutex<mpsc::UnboundedReceiver<Value>>,
+    }
+
+    impl ScriptedTransport {
+        fn new(results: Vec<Value>) -> Self {
+            let (response_tx, response_rx) = mpsc::unbounded_channel();
+            Self {
+                results: Mutex::new(results.into()),
+                response_tx,
+                response_rx: Mutex::new(response_rx),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Transport for ScriptedTransport {
+        async fn send<T: Serialize + Send + Sync>(&self, message: T) -> Result<()> {
+            let message = serde_json::to_value(&message)?;
+            // Notifications (no id) have nothing to reply to.
+            let Some(id) = message.get("id").and_then(Value::as_i64) else {
+                return Ok(());
+            };
+            let result =
+                self.results.lock().await.pop_front().ok_or_else(|| {
+                    anyhow::anyhow!("ScriptedTransport ran out of queued results")
+                })?;
+            let _ = self
+                .response_tx
+                .send(serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }));
+            Ok(())
+        }
+
+        async fn receive<T: DeserializeOwned + Send + Sync>(&self) -> Result<T> {
+            let value = self
+                .response_rx
+                .lock()
+                .await
+                .recv()
+                .await
+                .ok_or_else(|| anyhow::anyhow!("ScriptedTransport channel closed"))?;
+            Ok(serde_json::from_value(value)?)
+        }
+    }
+
+    #[tokio::test]
+    async fn read_resource_parses_spec_shaped_text_response() {
+        let client = McpClient::new(ScriptedTransport::new(vec![serde_json::json!({
+            "contents": [
+                { "uri": "file:///a.txt", "mimeType": "text/plain", "text": "hello" }
+            ]
+        })]));
+
+        let result = client.read_resource("file:///a.txt").await.unwrap();
+
+        assert_eq!(result.contents.len(), 1);
+        assert_eq!(result.contents[0].uri, "file:///a.txt");
+        assert_eq!(result.contents[0].mime_type.as_deref(), Some("text/plain"));
+        assert_eq!(result.contents[0].text.as_deref(), Some("hello"));
+        assert_eq!(result.contents[0].blob, None);
+    }
+
+    #[tokio::test]
+    async fn read_resource_parses_spec_shaped_blob_response() {
+        let client = McpClient::new(ScriptedTransport::new(vec![serde_json::json!({
+            "contents": [
+                { "uri": "file:///a.png", "mimeType": "image/png", "blob": "YmFzZTY0" }
+            ]
+        })]));
+
+        let result = client.read_resource("file:///a.png").await.unwrap();
+
+        assert_eq!(result.contents.len(), 1);
+        assert_eq!(result.contents[0].blob.as_deref(), Some("YmFzZTY0"));
+        assert_eq!(result.contents[0].text, None);
+    }
+
+    #[tokio::test]
+    async fn get_prompt_parses_spec_shaped_response() {
+        let client = McpClient::new(ScriptedTransport::new(vec![serde_json::json!({
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": "hi" } }
+            ]
+        })]));
+
+        let result = client.get_prompt("greeting", None).await.unwrap();
+
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(result.messages[0].role, "user");
+        assert!(matches!(&result.messages[0].content, Content::Text { text } if text == "hi"));
+    }
+
+    #[tokio::test]
+    async fn list_all_resources_follows_next_cursor() {
+        let client = McpClient::new(ScriptedTransport::new(vec![
+            serde_json::json!({
+                "resources": [{ "uri": "file:///a", "name": "a" }],
+                "nextCursor": "page2"
+            }),
+            serde_json::json!({
+                "resources": [{ "uri": "file:///b", "name": "b" }]
+            }),
+        ]));
+
+        let resources = client.list_all_resources().await.unwrap();
+
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].uri, "file:///a");
+        assert_eq!(resources[1].uri, "file:///b");
+    }
+
+    /// A transport whose `receive` never resolves, simulating a server that
+    /// never replies.
+    struct NonResponsiveTransport {
+        sent_cancellation: AtomicBool,
+    }
+
+    #[async_trait]
+    impl Transport for NonResponsiveTransport {
+        async fn send<T: Serialize + Send + Sync>(&self, message: T) -> Result<()> {
+            if let Ok(value) = serde_json::to_value(&message) {
+                if value.get("method").and_then(Value::as_str) == Some("notifications/cancelled") {
+                    self.sent_cancellation
+                        .store(true, std::sync::atomic::Ordering::SeqCst);
+                }
+            }
+            Ok(())
+        }
+
+        async fn receive<T: DeserializeOwned + Send + Sync>(&self) -> Result<T> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn request_times_out_instead_of_hanging() {
+        let client = McpClient::with_config(
+            NonResponsiveTransport {
+                sent_cancellation: AtomicBool::new(false),
+            },
+            McpClientConfig {
+                request_timeout: Duration::from_millis(20),
+            },
+        );
+
+        let result = client.list_tools().await;
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .downcast_ref::<RequestTimeoutError>()
+            .is_some());
+        assert!(client
+            .transport
+            .sent_cancellation
+            .load(std::sync::atomic::Ordering::SeqCst));
+    }
+}
@@ -1,7 +1,9 @@
-pub mod types;
-pub mod transport;
 pub mod client;
+pub mod server;
+pub mod transport;
+pub mod types;
 
-pub use types::*;
-pub use transport::*;
 pub use client::*;
+pub use server::*;
+pub use transport::*;
+pub use types::*;
@@ -0,0 +1,175 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::transport::Transport;
+use crate::types::*;
+
+type BoxFuture<'a, O> = Pin<Box<dyn Future<Output = O> + Send + 'a>>;
+
+type ToolHandler = Arc<dyn Fn(Value) -> BoxFuture<'static, Result<CallToolResult>> + Send + Sync>;
+
+struct RegisteredTool {
+    definition: ToolDefinition,
+    handler: ToolHandler,
+}
+
+/// Hosts MCP tools over a `Transport`, answering `initialize`, `tools/list`
+/// and `tools/call` requests from a connected client.
+pub struct McpServer<T: Transport> {
+    transport: Arc<T>,
+    tools: Mutex<HashMap<String, RegisteredTool>>,
+}
+
+impl<T: Transport + Send + Sync + 'static> McpServer<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport: Arc::new(transport),
+            tools: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register a tool that can be invoked via `tools/call`.
+    pub async fn register_tool<F, Fut>(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        input_schema: Value,
+        handler: F,
+    ) where
+        F: Fn(Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<CallToolResult>> + Send + 'static,
+    {
+        let definition = ToolDefinition {
+            name: name.to_string(),
+            description: description.map(|d| d.to_string()),
+            input_schema,
+        };
+        let handler: ToolHandler = Arc::new(move |args| Box::pin(handler(args)));
+
+        self.tools.lock().await.insert(
+            name.to_string(),
+            RegisteredTool {
+                definition,
+                handler,
+            },
+        );
+    }
+
+    /// Run the receive loop until the client sends `exit` or the transport
+    /// closes.
+    pub async fn run(&self) -> Result<()> {
+        loop {
+            let request = match self.transport.receive::<JsonRpcRequest>().await {
+                Ok(request) => request,
+                Err(e) => {
+                    eprintln!("MCP Server Transport Error: {:?}", e);
+                    return Err(e);
+                }
+            };
+
+            if request.method == "exit" {
+                return Ok(());
+            }
+
+            if let Some(response) = self.handle_request(request).await {
+                self.transport.send(response).await?;
+            }
+        }
+    }
+
+    /// Handle a single request, returning the response to send back (`None`
+    /// for notifications, which must not be replied to).
+    async fn handle_request(&self, request: JsonRpcRequest) -> Option<JsonRpcResponse> {
+        let id = request.id.clone()?;
+
+        let result = match request.method.as_str() {
+            // Minimal init for now, mirroring McpClient::initialize.
+            "initialize" => Ok(serde_json::json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "mcp-sdk-rust", "version": env!("CARGO_PKG_VERSION") }
+            })),
+            "notifications/initialized" => return None,
+            "shutdown" => Ok(Value::Null),
+            "tools/list" => self.handle_list_tools().await,
+            "tools/call" => self.handle_call_tool(request.params).await,
+            _ => Err(JsonRpcError {
+                code: -32601,
+                message: format!("Method not found: {}", request.method),
+                data: None,
+            }),
+        };
+
+        Some(match result {
+            Ok(result) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: Some(id),
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: Some(id),
+                result: None,
+                error: Some(error),
+            },
+        })
+    }
+
+    async fn handle_list_tools(&self) -> Result<Value, JsonRpcError> {
+        let tools = self.tools.lock().await;
+        let result = ListToolsResult {
+            tools: tools.values().map(|t| t.definition.clone()).collect(),
+            next_cursor: None,
+        };
+        serde_json::to_value(result).map_err(|e| JsonRpcError {
+            code: -32603,
+            message: e.to_string(),
+            data: None,
+        })
+    }
+
+    async fn handle_call_tool(&self, params: Option<Value>) -> Result<Value, JsonRpcError> {
+        let params = params.ok_or_else(|| JsonRpcError {
+            code: -32602,
+            message: "Missing params for tools/call".to_string(),
+            data: None,
+        })?;
+
+        let request: CallToolRequest =
+            serde_json::from_value(params).map_err(|e| JsonRpcError {
+                code: -32602,
+                message: format!("Invalid params for tools/call: {}", e),
+                data: None,
+            })?;
+
+        let handler = {
+            let tools = self.tools.lock().await;
+            tools
+                .get(&request.name)
+                .map(|t| t.handler.clone())
+                .ok_or_else(|| JsonRpcError {
+                    code: -32601,
+                    message: format!("Unknown tool: {}", request.name),
+                    data: None,
+                })?
+        };
+
+        let result = handler(request.arguments).await.map_err(|e| JsonRpcError {
+            code: -32603,
+            message: e.to_string(),
+            data: None,
+        })?;
+
+        serde_json::to_value(result).map_err(|e| JsonRpcError {
+            code: -32603,
+            message: e.to_string(),
+            data: None,
+        })
+    }
+}
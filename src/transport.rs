@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 
@@ -16,16 +16,116 @@ pub trait Transport {
     }
 }
 
-/// Transport over Stdio of a subprocess
-pub struct StdioTransport {
+/// Frames JSON-RPC messages onto and off of a byte stream. `StdioTransport`
+/// is generic over this so it can talk to servers that use either
+/// newline-delimited JSON or `Content-Length`-prefixed framing, without the
+/// client/type layers needing to know which.
+#[async_trait]
+pub trait Codec: Send + Sync {
+    /// Wrap a single JSON message's bytes for writing to the wire.
+    fn encode(&self, message: &[u8]) -> Vec<u8>;
+
+    /// Read exactly one framed message's JSON bytes off the reader.
+    async fn read_frame(
+        &self,
+        reader: &mut BufReader<tokio::process::ChildStdout>,
+    ) -> Result<Vec<u8>>;
+}
+
+/// One JSON value per line, as used by most MCP stdio servers today.
+pub struct LineDelimited;
+
+#[async_trait]
+impl Codec for LineDelimited {
+    fn encode(&self, message: &[u8]) -> Vec<u8> {
+        let mut framed = message.to_vec();
+        framed.push(b'\n');
+        framed
+    }
+
+    async fn read_frame(
+        &self,
+        reader: &mut BufReader<tokio::process::ChildStdout>,
+    ) -> Result<Vec<u8>> {
+        let mut line = String::new();
+        let bytes_read = reader.read_line(&mut line).await?;
+        if bytes_read == 0 {
+            return Err(anyhow::anyhow!("MCP Server closed connection (EOF)"));
+        }
+        Ok(line.into_bytes())
+    }
+}
+
+/// LSP-style `Content-Length: N\r\n\r\n<bytes>` framing, unambiguous even if
+/// the payload is pretty-printed or contains embedded newlines.
+pub struct ContentLength;
+
+#[async_trait]
+impl Codec for ContentLength {
+    fn encode(&self, message: &[u8]) -> Vec<u8> {
+        let mut framed = format!("Content-Length: {}\r\n\r\n", message.len()).into_bytes();
+        framed.extend_from_slice(message);
+        framed
+    }
+
+    async fn read_frame(
+        &self,
+        reader: &mut BufReader<tokio::process::ChildStdout>,
+    ) -> Result<Vec<u8>> {
+        let mut content_length: Option<usize> = None;
+        loop {
+            let mut header = String::new();
+            let bytes_read = reader.read_line(&mut header).await?;
+            if bytes_read == 0 {
+                return Err(anyhow::anyhow!("MCP Server closed connection (EOF)"));
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                // Tolerate extra headers (e.g. Content-Type); only
+                // Content-Length affects framing.
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    content_length = Some(
+                        value
+                            .trim()
+                            .parse()
+                            .context("Invalid Content-Length header")?,
+                    );
+                }
+            }
+        }
+
+        let content_length = content_length.context("Missing Content-Length header")?;
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).await?;
+        Ok(body)
+    }
+}
+
+/// Transport over Stdio of a subprocess. Generic over the wire framing so
+/// the same transport can speak either newline-delimited JSON or
+/// `Content-Length`-prefixed framing.
+pub struct StdioTransport<C: Codec = LineDelimited> {
     #[allow(dead_code)]
     child: Mutex<Child>,
     reader: Mutex<BufReader<tokio::process::ChildStdout>>,
     writer: Mutex<tokio::process::ChildStdin>,
+    codec: C,
 }
 
-impl StdioTransport {
+impl StdioTransport<LineDelimited> {
+    /// Spawn `command` and talk to it over newline-delimited JSON, the
+    /// framing most MCP stdio servers use.
     pub fn new(command: &str, args: &[&str]) -> Result<Self> {
+        Self::with_codec(command, args, LineDelimited)
+    }
+}
+
+impl<C: Codec> StdioTransport<C> {
+    /// Spawn `command` and talk to it using the given `codec` for framing.
+    pub fn with_codec(command: &str, args: &[&str], codec: C) -> Result<Self> {
         let mut cmd = Command::new(command);
         cmd.args(args)
             .stdin(Stdio::piped())
@@ -41,30 +141,31 @@ impl StdioTransport {
             child: Mutex::new(child),
             reader: Mutex::new(BufReader::new(stdout)),
             writer: Mutex::new(stdin),
+            codec,
         })
     }
 }
 
 #[async_trait]
-impl Transport for StdioTransport {
+impl<C: Codec> Transport for StdioTransport<C> {
     async fn send<T: Serialize + Send + Sync>(&self, message: T) -> Result<()> {
-        let json = serde_json::to_string(&message)?;
+        let json = serde_json::to_vec(&message)?;
+        let framed = self.codec.encode(&json);
         let mut writer = self.writer.lock().await;
-        writer.write_all(json.as_bytes()).await?;
-        writer.write_all(b"\n").await?;
+        writer.write_all(&framed).await?;
         writer.flush().await?;
         Ok(())
     }
 
     async fn receive<T: DeserializeOwned + Send + Sync>(&self) -> Result<T> {
         let mut reader = self.reader.lock().await;
-        let mut line = String::new();
-        let bytes_read = reader.read_line(&mut line).await?;
-        if bytes_read == 0 {
-            return Err(anyhow::anyhow!("MCP Server closed connection (EOF)"));
-        }
-        let message: T = serde_json::from_str(&line)
-            .with_context(|| format!("Failed to parse MCP message: {}", line))?;
+        let bytes = self.codec.read_frame(&mut reader).await?;
+        let message: T = serde_json::from_slice(&bytes).with_context(|| {
+            format!(
+                "Failed to parse MCP message: {}",
+                String::from_utf8_lossy(&bytes)
+            )
+        })?;
         Ok(message)
     }
 
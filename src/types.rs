@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 /// JSON-RPC 2.0 Request
@@ -31,8 +31,79 @@ pub struct JsonRpcError {
     pub data: Option<Value>,
 }
 
+/// A message read off the wire before it is known whether it is a reply to
+/// one of our own requests, a notification, or a server-initiated request.
+///
+/// A message with `method` and an `id` is a server→client request and is
+/// expected to get a `JsonRpcResponse` back; a message with `method` but no
+/// `id` is a notification and must not be replied to. Anything without `method` is a
+/// response to a request we sent.
+#[derive(Debug)]
+pub enum IncomingMessage {
+    Response(JsonRpcResponse),
+    Request(JsonRpcRequest),
+    Notification(JsonRpcRequest),
+}
+
+impl<'de> Deserialize<'de> for IncomingMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        if value.get("method").is_some() {
+            let request: JsonRpcRequest =
+                serde_json::from_value(value).map_err(serde::de::Error::custom)?;
+            if request.id.is_some() {
+                Ok(IncomingMessage::Request(request))
+            } else {
+                Ok(IncomingMessage::Notification(request))
+            }
+        } else {
+            let response: JsonRpcResponse =
+                serde_json::from_value(value).map_err(serde::de::Error::custom)?;
+            Ok(IncomingMessage::Response(response))
+        }
+    }
+}
+
 // --- MCP Specific Payloads ---
 
+/// Protocol versions this crate knows how to speak. `initialize` responses
+/// whose `protocolVersion` isn't in this set are rejected up front, rather
+/// than failing later with an opaque parse error the first time a
+/// version-specific field is missing.
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26"];
+
+/// Result for `initialize`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeResult {
+    pub protocol_version: String,
+    #[serde(default)]
+    pub capabilities: ServerCapabilities,
+    pub server_info: ServerInfo,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerCapabilities {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub name: String,
+    pub version: String,
+}
+
 /// Payload for tools/call
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CallToolRequest {
@@ -65,7 +136,7 @@ pub struct ListToolsResult {
     pub next_cursor: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolDefinition {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -79,6 +150,105 @@ impl ToolDefinition {
     }
 }
 
+/// A resource a server can expose for reading via `resources/read`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    pub uri: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Result for resources/list
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResourcesResult {
+    pub resources: Vec<Resource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Payload for resources/read
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadResourceRequest {
+    pub uri: String,
+}
+
+/// Result for resources/read
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadResourceResult {
+    pub contents: Vec<ResourceContents>,
+}
+
+/// The contents of a single resource. Exactly one of `text`/`blob` is set,
+/// depending on whether the resource is text or binary; this is the shape
+/// `resources/read` actually sends on the wire, with no `type` tag to
+/// discriminate (unlike `Content`, which tool results use).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+/// A prompt template a server can expose via `prompts/get`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prompt {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<PromptArgument>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Result for prompts/list
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPromptsResult {
+    pub prompts: Vec<Prompt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Payload for prompts/get
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetPromptRequest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Value>,
+}
+
+/// Result for prompts/get
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetPromptResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub messages: Vec<PromptMessage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PromptMessage {
+    pub role: String,
+    pub content: Content,
+}
+
 impl CallToolResult {
     /// Convert the mixed content of the result into a single text representation.
     /// Text content is appended directly. Images and resources are represented by placeholders.
@@ -89,10 +259,10 @@ impl CallToolResult {
                 Content::Text { text } => {
                     text_output.push_str(text);
                     text_output.push('\n');
-                },
+                }
                 Content::Image { .. } => {
                     text_output.push_str("[Image Content from MCP Tool]\n");
-                },
+                }
                 Content::EmbeddedResource { .. } => {
                     text_output.push_str("[Embedded Resource from MCP Tool]\n");
                 }